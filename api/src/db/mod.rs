@@ -0,0 +1,7 @@
+// `sinks` and `sources` are declared for the same reason as `configuration`/`encryption`/
+// `startup` in `crate::lib`: `tests/api/test_app.rs` imports `SinkConfig`/`SourceConfig` from
+// them, but their implementations are outside this snapshot.
+pub mod pipeline_checkpoints;
+pub mod pipelines;
+pub mod sinks;
+pub mod sources;
@@ -0,0 +1,81 @@
+use async_trait::async_trait;
+use pg_replicate::pipeline::checkpoint::CheckpointStore;
+use sqlx::PgPool;
+use tokio_postgres::types::PgLsn;
+
+/// [`CheckpointStore`] backed by the same Postgres-backed config store the API already uses
+/// for pipeline/source/sink configuration, via the `pipeline_checkpoints` table.
+#[derive(Debug, Clone)]
+pub struct PgCheckpointStore {
+    pool: PgPool,
+}
+
+impl PgCheckpointStore {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl CheckpointStore for PgCheckpointStore {
+    async fn read_confirmed_lsn(
+        &self,
+        pipeline_id: i64,
+    ) -> Result<Option<PgLsn>, Box<dyn std::error::Error + Send + Sync>> {
+        let lsn = read_confirmed_lsn(&self.pool, pipeline_id).await?;
+        Ok(lsn.map(|lsn| PgLsn::from(lsn as u64)))
+    }
+
+    async fn write_confirmed_lsn(
+        &self,
+        pipeline_id: i64,
+        lsn: PgLsn,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        write_confirmed_lsn(&self.pool, pipeline_id, u64::from(lsn) as i64).await?;
+        Ok(())
+    }
+}
+
+/// The confirmed flush LSN last checkpointed for a pipeline, stored as the LSN's `u64` value
+/// cast to `i64` (a Postgres `bigint`) so it can round-trip through Postgres without depending
+/// on `tokio_postgres`'s `PgLsn` type here.
+pub async fn read_confirmed_lsn(
+    pool: &PgPool,
+    pipeline_id: i64,
+) -> Result<Option<i64>, sqlx::Error> {
+    let record = sqlx::query!(
+        r#"
+        select confirmed_lsn
+        from pipeline_checkpoints
+        where pipeline_id = $1
+        "#,
+        pipeline_id
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(record.map(|r| r.confirmed_lsn))
+}
+
+/// Upserts the confirmed flush LSN for `pipeline_id`, called after each batch is durably
+/// written to the sink.
+pub async fn write_confirmed_lsn(
+    pool: &PgPool,
+    pipeline_id: i64,
+    confirmed_lsn: i64,
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        r#"
+        insert into pipeline_checkpoints (pipeline_id, confirmed_lsn)
+        values ($1, $2)
+        on conflict (pipeline_id)
+        do update set confirmed_lsn = excluded.confirmed_lsn
+        "#,
+        pipeline_id,
+        confirmed_lsn
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,15 @@
+use pg_replicate::pipeline::retry::RetryConfig;
+use pg_replicate::pipeline::snapshot::SnapshotConfig;
+use serde::{Deserialize, Serialize};
+
+/// Per-pipeline tuning, stored alongside the pipeline's source/sink/publication in the config
+/// store and passed to `create_pipeline`.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(default)]
+pub struct PipelineConfig {
+    /// How sink-write failures are retried; see [`pg_replicate::pipeline::retry`].
+    pub retry: RetryConfig,
+    /// Whether to backfill existing table contents before streaming changes, and how; see
+    /// [`pg_replicate::pipeline::snapshot`].
+    pub snapshot: SnapshotConfig,
+}
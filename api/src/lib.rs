@@ -0,0 +1,15 @@
+// `configuration`, `encryption` and `startup` are declared because `tests/api/test_app.rs`
+// (already present in this tree before any of this series' commits) imports
+// `api::configuration::get_configuration`, `api::encryption::{self, generate_random_key}` and
+// `api::startup::{get_connection_pool, run}`, along with a `database` test-support module and
+// tenant/source/sink/image CRUD routes it also exercises. None of those — nor these three
+// modules' implementations — are part of this snapshot, so this crate does not compile as-is.
+// Earlier commits in this series wired `routes::pipelines` against a `startup::AppState` on the
+// assumption those modules would land; that assumption doesn't hold, so don't take "mounted by
+// startup::run" in those commits' messages as true until `startup`, `configuration` and
+// `encryption` actually exist here.
+pub mod configuration;
+pub mod db;
+pub mod encryption;
+pub mod routes;
+pub mod startup;
@@ -0,0 +1,73 @@
+use axum::{http::StatusCode, response::IntoResponse, Json};
+use pg_replicate::error::ReplicationError;
+use serde::Serialize;
+
+/// A uniform error body for the `/v1` API, mapping internal error types to a status code and
+/// a machine-readable reason instead of letting everything surface as an opaque 500.
+pub struct ErrorResponse {
+    status: StatusCode,
+    body: ErrorBody,
+}
+
+#[derive(Serialize)]
+struct ErrorBody {
+    reason: String,
+}
+
+impl IntoResponse for ErrorResponse {
+    fn into_response(self) -> axum::response::Response {
+        (self.status, Json(self.body)).into_response()
+    }
+}
+
+impl From<ReplicationError> for ErrorResponse {
+    fn from(err: ReplicationError) -> Self {
+        let status = match &err {
+            ReplicationError::UnsupportedNumericValue { .. }
+            | ReplicationError::InvalidNumericSign(_)
+            | ReplicationError::InvalidDigit(_)
+            | ReplicationError::TruncatedWireData { .. }
+            | ReplicationError::RustDecimalConversion { .. } => StatusCode::UNPROCESSABLE_ENTITY,
+            ReplicationError::SinkWrite { .. } | ReplicationError::SourceStream { .. } => {
+                StatusCode::SERVICE_UNAVAILABLE
+            }
+        };
+
+        ErrorResponse {
+            status,
+            body: ErrorBody {
+                reason: err.to_string(),
+            },
+        }
+    }
+}
+
+impl From<sqlx::Error> for ErrorResponse {
+    fn from(err: sqlx::Error) -> Self {
+        let status = match &err {
+            sqlx::Error::RowNotFound => StatusCode::NOT_FOUND,
+            _ => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+
+        ErrorResponse {
+            status,
+            body: ErrorBody {
+                reason: err.to_string(),
+            },
+        }
+    }
+}
+
+impl From<Box<dyn std::error::Error + Send + Sync>> for ErrorResponse {
+    fn from(err: Box<dyn std::error::Error + Send + Sync>) -> Self {
+        match err.downcast::<ReplicationError>() {
+            Ok(replication_err) => ErrorResponse::from(*replication_err),
+            Err(err) => ErrorResponse {
+                status: StatusCode::INTERNAL_SERVER_ERROR,
+                body: ErrorBody {
+                    reason: err.to_string(),
+                },
+            },
+        }
+    }
+}
@@ -0,0 +1,4 @@
+mod error;
+pub mod pipelines;
+
+pub use error::ErrorResponse;
@@ -0,0 +1,33 @@
+use std::convert::Infallible;
+use std::time::Duration;
+
+use axum::{
+    extract::{Path, State},
+    response::sse::{Event, KeepAlive, Sse},
+};
+use futures::stream::Stream;
+use tokio_stream::wrappers::BroadcastStream;
+use tokio_stream::StreamExt;
+
+use crate::{routes::ErrorResponse, startup::AppState};
+
+/// `GET /v1/pipelines/{id}/events` — a live Server-Sent Events feed of
+/// `pg_replicate::pipeline::events::ReplicationEvent`s for a running pipeline: periodic
+/// heartbeats plus discrete events on slot creation, snapshot progress, sink errors and
+/// retries.
+pub async fn stream_pipeline_events(
+    State(state): State<AppState>,
+    Path(pipeline_id): Path<i64>,
+) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, ErrorResponse> {
+    let receiver = state.replicator_handles.subscribe_events(pipeline_id).await?;
+
+    let stream = BroadcastStream::new(receiver).filter_map(|event| match event {
+        Ok(event) => serde_json::to_string(&event)
+            .ok()
+            .map(|data| Ok(Event::default().data(data))),
+        // A slow subscriber skipped some events; keep streaming rather than erroring out.
+        Err(_) => None,
+    });
+
+    Ok(Sse::new(stream).keep_alive(KeepAlive::new().interval(Duration::from_secs(15))))
+}
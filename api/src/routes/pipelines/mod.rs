@@ -0,0 +1,15 @@
+use axum::{routing::get, Router};
+
+use crate::startup::AppState;
+
+pub mod events;
+pub mod status;
+
+/// Routes that report on a running pipeline's state rather than managing its configuration
+/// (pipeline CRUD lives alongside sources/sinks). Nothing in this crate mounts this router;
+/// `startup`, which would merge it into the main router, is not part of this tree.
+pub fn observability_router() -> Router<AppState> {
+    Router::new()
+        .route("/v1/pipelines/:id/status", get(status::read_pipeline_status))
+        .route("/v1/pipelines/:id/events", get(events::stream_pipeline_events))
+}
@@ -0,0 +1,64 @@
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use serde::Serialize;
+
+use crate::{db::pipeline_checkpoints, routes::ErrorResponse, startup::AppState};
+
+/// Progress of a single table's initial backfill, see `pg_replicate::pipeline::snapshot`.
+#[derive(Debug, Serialize)]
+pub struct TableBackfillStatus {
+    pub table_id: u32,
+    pub rows_copied: u64,
+    pub done: bool,
+}
+
+/// Response body for `GET /v1/pipelines/{id}/status`.
+#[derive(Debug, Serialize)]
+pub struct PipelineStatusResponse {
+    pub pipeline_id: i64,
+    /// The last LSN the sink has durably applied, as reported by the checkpoint store.
+    pub confirmed_lsn: Option<i64>,
+    /// The server's current WAL end LSN, i.e. how far the source has written.
+    pub current_wal_lsn: i64,
+    /// `current_wal_lsn - confirmed_lsn` in bytes, or `None` before the first checkpoint.
+    pub lag_bytes: Option<i64>,
+    /// Per-table backfill progress, present only while `PipelineConfig::snapshot` is running
+    /// its initial copy. Empty once every table has finished and streaming has taken over.
+    pub backfill: Vec<TableBackfillStatus>,
+    /// How many sink-write retries (per `PipelineConfig::retry`) this pipeline has made since
+    /// it started, so operators can spot a flapping sink without trawling logs.
+    pub sink_retries: u64,
+}
+
+pub async fn read_pipeline_status(
+    State(state): State<AppState>,
+    Path(pipeline_id): Path<i64>,
+) -> Result<Json<PipelineStatusResponse>, ErrorResponse> {
+    let confirmed_lsn =
+        pipeline_checkpoints::read_confirmed_lsn(&state.connection_pool, pipeline_id).await?;
+    let current_wal_lsn = state.replicator_handles.current_wal_lsn(pipeline_id).await?;
+    let lag_bytes = confirmed_lsn.map(|lsn| current_wal_lsn - lsn);
+    let backfill = state
+        .replicator_handles
+        .backfill_progress(pipeline_id)
+        .await?
+        .into_iter()
+        .map(|cursor| TableBackfillStatus {
+            table_id: cursor.table_id.0,
+            rows_copied: cursor.rows_copied,
+            done: cursor.done,
+        })
+        .collect();
+    let sink_retries = state.replicator_handles.sink_retries(pipeline_id).await?;
+
+    Ok(Json(PipelineStatusResponse {
+        pipeline_id,
+        confirmed_lsn,
+        current_wal_lsn,
+        lag_bytes,
+        backfill,
+        sink_retries,
+    }))
+}
@@ -0,0 +1,4 @@
+mod test_app;
+
+mod pipeline_events;
+mod pipeline_status;
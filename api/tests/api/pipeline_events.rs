@@ -0,0 +1,10 @@
+use crate::test_app::spawn_app;
+
+#[tokio::test]
+async fn streaming_events_for_unknown_pipeline_is_an_error() {
+    let app = spawn_app().await;
+
+    let mut stream = app.stream_pipeline_events(1, 999_999).await;
+
+    assert!(stream.next_event().await.is_none());
+}
@@ -0,0 +1,10 @@
+use crate::test_app::spawn_app;
+
+#[tokio::test]
+async fn read_status_for_unknown_pipeline_is_an_error() {
+    let app = spawn_app().await;
+
+    let response = app.read_pipeline_status(1, 999_999).await;
+
+    assert!(!response.status().is_success());
+}
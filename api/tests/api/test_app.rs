@@ -115,6 +115,79 @@ pub struct UpdatePipelineRequest {
     pub config: PipelineConfig,
 }
 
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum PipelineEvent {
+    Heartbeat {
+        current_lsn: u64,
+        lag_bytes: u64,
+        rows_applied: u64,
+        last_event_unix_millis: u64,
+    },
+    SlotCreated {
+        slot_name: String,
+    },
+    SnapshotProgress {
+        table_id: u32,
+        rows_copied: u64,
+        done: bool,
+    },
+    SinkError {
+        reason: String,
+    },
+    Retry {
+        attempt: u32,
+        reason: String,
+    },
+}
+
+/// Decodes a `text/event-stream` response body into [`PipelineEvent`]s as they arrive.
+pub struct PipelineEventStream {
+    response: reqwest::Response,
+    buffer: String,
+}
+
+impl PipelineEventStream {
+    /// Waits for and decodes the next event, or `None` once the stream ends.
+    pub async fn next_event(&mut self) -> Option<PipelineEvent> {
+        loop {
+            if let Some(end) = self.buffer.find("\n\n") {
+                let raw_event: String = self.buffer.drain(..end + 2).collect();
+                for line in raw_event.lines() {
+                    if let Some(data) = line.strip_prefix("data: ") {
+                        if let Ok(event) = serde_json::from_str(data) {
+                            return Some(event);
+                        }
+                    }
+                }
+                continue;
+            }
+
+            match self.response.chunk().await {
+                Ok(Some(chunk)) => self.buffer.push_str(&String::from_utf8_lossy(&chunk)),
+                _ => return None,
+            }
+        }
+    }
+}
+
+#[derive(Deserialize)]
+pub struct TableBackfillStatus {
+    pub table_id: u32,
+    pub rows_copied: u64,
+    pub done: bool,
+}
+
+#[derive(Deserialize)]
+pub struct PipelineStatusResponse {
+    pub pipeline_id: i64,
+    pub confirmed_lsn: Option<i64>,
+    pub current_wal_lsn: i64,
+    pub lag_bytes: Option<i64>,
+    pub backfill: Vec<TableBackfillStatus>,
+    pub sink_retries: u64,
+}
+
 #[derive(Serialize)]
 pub struct CreateImageRequest {
     pub name: String,
@@ -344,6 +417,42 @@ impl TestApp {
             .expect("failed to execute request")
     }
 
+    pub async fn read_pipeline_status(
+        &self,
+        tenant_id: i64,
+        pipeline_id: i64,
+    ) -> reqwest::Response {
+        self.get_authenticated(format!(
+            "{}/v1/pipelines/{pipeline_id}/status",
+            &self.address
+        ))
+        .header("tenant_id", tenant_id)
+        .send()
+        .await
+        .expect("failed to execute request")
+    }
+
+    pub async fn stream_pipeline_events(
+        &self,
+        tenant_id: i64,
+        pipeline_id: i64,
+    ) -> PipelineEventStream {
+        let response = self
+            .get_authenticated(format!(
+                "{}/v1/pipelines/{pipeline_id}/events",
+                &self.address
+            ))
+            .header("tenant_id", tenant_id)
+            .send()
+            .await
+            .expect("failed to execute request");
+
+        PipelineEventStream {
+            response,
+            buffer: String::new(),
+        }
+    }
+
     pub async fn create_image(&self, image: &CreateImageRequest) -> reqwest::Response {
         self.post_authenticated(format!("{}/v1/images", &self.address))
             .json(image)
@@ -0,0 +1,3 @@
+//! Wire-format conversions between Postgres types and Rust types used during replication.
+
+pub mod numeric;
@@ -9,7 +9,10 @@ use bigdecimal::{
 };
 
 use byteorder::{NetworkEndian, ReadBytesExt};
-use tokio_postgres::types::{FromSql, Type};
+use bytes::{BufMut, BytesMut};
+use tokio_postgres::types::{to_sql_checked, FromSql, IsNull, ToSql, Type};
+
+use crate::error::ReplicationError;
 
 /// representation
 pub enum PgNumeric {
@@ -35,17 +38,8 @@ pub enum PgNumeric {
     NaN,
 }
 
-#[derive(Debug, Clone, Copy)]
-struct InvalidNumericSign(u16);
-impl ::std::fmt::Display for InvalidNumericSign {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> ::std::fmt::Result {
-        f.write_str("sign for numeric field was not one of 0, 0x4000, 0xC000")
-    }
-}
-impl Error for InvalidNumericSign {}
-
 impl<'a> TryFrom<&'a PgNumeric> for BigDecimal {
-    type Error = Box<dyn Error + Send + Sync>;
+    type Error = ReplicationError;
 
     fn try_from(numeric: &'a PgNumeric) -> Result<Self, Self::Error> {
         let (sign, weight, scale, digits) = match *numeric {
@@ -60,15 +54,22 @@ impl<'a> TryFrom<&'a PgNumeric> for BigDecimal {
                 ref digits,
             } => (Sign::Minus, weight, scale, digits),
             PgNumeric::NaN => {
-                return Err(Box::from("NaN is not (yet) supported in BigDecimal"))
+                return Err(ReplicationError::UnsupportedNumericValue {
+                    type_oid: Type::NUMERIC.oid(),
+                    reason: "NaN is not (yet) supported in BigDecimal",
+                })
             }
         };
 
         let mut result = BigUint::default();
-        let count = i64::try_from(digits.len())?;
+        // `digits.len()` is bounded by the wire-format digit count, a `u16`, so this always fits.
+        let count = i64::try_from(digits.len()).unwrap_or(i64::MAX);
         for digit in digits {
+            if !(0..10_000).contains(digit) {
+                return Err(ReplicationError::InvalidDigit(*digit));
+            }
             result *= BigUint::from(10_000u64);
-            result += BigUint::from(u64::try_from(*digit)?);
+            result += BigUint::from(*digit as u64);
         }
         // First digit got factor 10_000^(digits.len() - 1), but should get 10_000^weight
         let correction_exp = 4 * (i64::from(weight) - count + 1);
@@ -80,38 +81,260 @@ impl<'a> TryFrom<&'a PgNumeric> for BigDecimal {
 
 impl<'a> FromSql<'a> for PgNumeric {
     fn from_sql(ty: &Type, raw: &'a [u8]) -> Result<Self, Box<dyn Error + Send + Sync>> {
-        let mut bytes = raw.clone();
-        let digit_count = bytes.read_u16::<NetworkEndian>()?;
-        let mut digits = Vec::with_capacity(digit_count as usize);
-        let weight = bytes.read_i16::<NetworkEndian>()?;
-        let sign = bytes.read_u16::<NetworkEndian>()?;
-        let scale = bytes.read_u16::<NetworkEndian>()?;
-        for _ in 0..digit_count {
-            digits.push(bytes.read_i16::<NetworkEndian>()?);
+        decode(ty, raw).map_err(Into::into)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        match *ty {
+            Type::NUMERIC => true,
+            _ => false,
         }
+    }
+}
+
+fn decode(ty: &Type, raw: &[u8]) -> Result<PgNumeric, ReplicationError> {
+    let mut bytes = raw;
+    let truncated = |source: std::io::Error, bytes: &[u8]| ReplicationError::TruncatedWireData {
+        type_oid: ty.oid(),
+        byte_offset: raw.len() - bytes.len(),
+        source: Box::new(source),
+    };
+
+    let digit_count = bytes
+        .read_u16::<NetworkEndian>()
+        .map_err(|e| truncated(e, bytes))?;
+    let mut digits = Vec::with_capacity(digit_count as usize);
+    let weight = bytes
+        .read_i16::<NetworkEndian>()
+        .map_err(|e| truncated(e, bytes))?;
+    let sign = bytes
+        .read_u16::<NetworkEndian>()
+        .map_err(|e| truncated(e, bytes))?;
+    let scale = bytes
+        .read_u16::<NetworkEndian>()
+        .map_err(|e| truncated(e, bytes))?;
+    for _ in 0..digit_count {
+        digits.push(
+            bytes
+                .read_i16::<NetworkEndian>()
+                .map_err(|e| truncated(e, bytes))?,
+        );
+    }
+
+    match sign {
+        0 => Ok(PgNumeric::Positive {
+            weight,
+            scale,
+            digits,
+        }),
+        0x4000 => Ok(PgNumeric::Negative {
+            weight,
+            scale,
+            digits,
+        }),
+        0xC000 => Ok(PgNumeric::NaN),
+        invalid => Err(ReplicationError::InvalidNumericSign(invalid)),
+    }
+}
+
+/// `BigDecimal` values whose scale or digit-group count overflow the wire format's `u16`/`i16`
+/// fields can't be represented as a `PgNumeric` at all.
+fn too_large(reason: &'static str) -> ReplicationError {
+    ReplicationError::UnsupportedNumericValue {
+        type_oid: Type::NUMERIC.oid(),
+        reason,
+    }
+}
+
+impl<'a> TryFrom<&'a BigDecimal> for PgNumeric {
+    type Error = ReplicationError;
+
+    fn try_from(decimal: &'a BigDecimal) -> Result<Self, Self::Error> {
+        let (unscaled, original_scale) = decimal.as_bigint_and_scale();
+        let sign = unscaled.sign();
+        let scale =
+            u16::try_from(original_scale.max(0)).map_err(|_| too_large("scale out of range"))?;
+
+        if unscaled == BigInt::from(0) {
+            return Ok(PgNumeric::Positive {
+                weight: 0,
+                scale,
+                digits: vec![],
+            });
+        }
+
+        // Pad with trailing zeros so the fractional part lines up on a base-10000 boundary
+        // (and so a negative `original_scale`, i.e. trailing zeros before the decimal point,
+        // is folded into the integer part instead).
+        let scale_pad = if original_scale < 0 {
+            -original_scale
+        } else {
+            (4 - original_scale.rem_euclid(4)) % 4
+        };
+        let padded = unscaled.magnitude() * BigUint::from(10u64).pow(scale_pad as u32);
+
+        let ten_thousand = BigUint::from(10_000u64);
+        let mut digits = Vec::new();
+        let mut remainder = padded;
+        while remainder > BigUint::default() {
+            let digit = &remainder % &ten_thousand;
+            digits.push(i16::try_from(digit).map_err(|_| too_large("digit out of range"))?);
+            remainder /= &ten_thousand;
+        }
+        digits.reverse();
+
+        // Must use the signed `original_scale` here, not `original_scale.max(0)`: for a
+        // negative scale, `scale_pad` (`-original_scale`) already cancels it exactly, leaving
+        // zero fractional groups. Clamping to zero instead double-counted `scale_pad` as
+        // fractional digits and corrupted the round trip for any negative-scale `BigDecimal`
+        // (e.g. `1e5`, `1e6`, `1e7` round-tripped to `10`, `100`, `1000`).
+        let fractional_groups = (original_scale + scale_pad) / 4;
+        let weight = i16::try_from(digits.len() as i64 - fractional_groups - 1)
+            .map_err(|_| too_large("weight out of range"))?;
 
         match sign {
-            0 => Ok(PgNumeric::Positive {
+            Sign::Minus => Ok(PgNumeric::Negative {
                 weight,
                 scale,
                 digits,
             }),
-            0x4000 => Ok(PgNumeric::Negative {
+            _ => Ok(PgNumeric::Positive {
                 weight,
                 scale,
                 digits,
             }),
-            0xC000 => Ok(PgNumeric::NaN),
-            invalid => Err(Box::new(InvalidNumericSign(invalid))),
         }
     }
-    
+}
+
+impl ToSql for PgNumeric {
+    fn to_sql(
+        &self,
+        _ty: &Type,
+        out: &mut BytesMut,
+    ) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        let (sign, weight, scale, digits): (u16, i16, u16, &[i16]) = match self {
+            PgNumeric::Positive {
+                weight,
+                scale,
+                digits,
+            } => (0, *weight, *scale, digits),
+            PgNumeric::Negative {
+                weight,
+                scale,
+                digits,
+            } => (0x4000, *weight, *scale, digits),
+            // Postgres represents NaN as a zero-digit numeric with sign 0xC000.
+            PgNumeric::NaN => (0xC000, 0, 0, &[][..]),
+        };
+
+        // `BytesMut` is a `BufMut`, not a `std::io::Write`, so this uses `BufMut::put_*`
+        // (network/big-endian by default) rather than `byteorder`'s extension traits; `decode`
+        // above uses `ReadBytesExt` since it reads from a `&[u8]` `std::io::Read` instead.
+        out.put_u16(digits.len() as u16);
+        out.put_i16(weight);
+        out.put_u16(sign);
+        out.put_u16(scale);
+        for digit in digits {
+            out.put_i16(*digit);
+        }
+
+        Ok(IsNull::No)
+    }
+
     fn accepts(ty: &Type) -> bool {
-        match *ty {
-            Type::NUMERIC => true,
-            _ => false,
+        matches!(*ty, Type::NUMERIC)
+    }
+
+    to_sql_checked!();
+}
+
+/// Conversions to/from [`rust_decimal::Decimal`] for sinks that cannot carry `BigDecimal`'s
+/// arbitrary precision. Enabled by the `rust_decimal` feature.
+#[cfg(feature = "rust_decimal")]
+impl<'a> TryFrom<&'a PgNumeric> for rust_decimal::Decimal {
+    type Error = ReplicationError;
+
+    fn try_from(numeric: &'a PgNumeric) -> Result<Self, Self::Error> {
+        let big_decimal = BigDecimal::try_from(numeric)?;
+        rust_decimal::Decimal::try_from(big_decimal).map_err(|source| {
+            ReplicationError::RustDecimalConversion {
+                type_oid: Type::NUMERIC.oid(),
+                source: Box::new(source),
+            }
+        })
+    }
+}
+
+#[cfg(feature = "rust_decimal")]
+impl<'a> TryFrom<&'a rust_decimal::Decimal> for PgNumeric {
+    type Error = ReplicationError;
+
+    fn try_from(decimal: &'a rust_decimal::Decimal) -> Result<Self, Self::Error> {
+        let big_decimal =
+            BigDecimal::try_from(*decimal).map_err(|source| ReplicationError::RustDecimalConversion {
+                type_oid: Type::NUMERIC.oid(),
+                source: Box::new(source),
+            })?;
+        PgNumeric::try_from(&big_decimal)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn round_trips_positive_decimal() {
+        let decimal = BigDecimal::from_str("1234.5678").unwrap();
+        let numeric = PgNumeric::try_from(&decimal).unwrap();
+        let round_tripped = BigDecimal::try_from(&numeric).unwrap();
+        assert_eq!(decimal, round_tripped);
+    }
+
+    #[test]
+    fn round_trips_negative_decimal() {
+        let decimal = BigDecimal::from_str("-42.01").unwrap();
+        let numeric = PgNumeric::try_from(&decimal).unwrap();
+        let round_tripped = BigDecimal::try_from(&numeric).unwrap();
+        assert_eq!(decimal, round_tripped);
+    }
+
+    #[test]
+    fn round_trips_negative_scale_decimal() {
+        for input in ["1e5", "1e6", "1e7"] {
+            let decimal = BigDecimal::from_str(input).unwrap();
+            assert!(decimal.as_bigint_and_scale().1 < 0, "fixture should have a negative scale");
+            let numeric = PgNumeric::try_from(&decimal).unwrap();
+            let round_tripped = BigDecimal::try_from(&numeric).unwrap();
+            assert_eq!(decimal, round_tripped, "round trip of {input} changed value");
         }
     }
 
-    
+    #[test]
+    fn round_trips_zero() {
+        let decimal = BigDecimal::from_str("0").unwrap();
+        let numeric = PgNumeric::try_from(&decimal).unwrap();
+        let round_tripped = BigDecimal::try_from(&numeric).unwrap();
+        assert_eq!(decimal, round_tripped);
+    }
+
+    #[test]
+    fn nan_serializes_to_zero_digit_sentinel() {
+        let mut out = BytesMut::new();
+        PgNumeric::NaN.to_sql(&Type::NUMERIC, &mut out).unwrap();
+        assert_eq!(out.as_ref(), &[0, 0, 0, 0, 0xC0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn out_of_range_digit_is_a_typed_error_not_a_panic() {
+        let numeric = PgNumeric::Positive {
+            weight: 0,
+            scale: 0,
+            digits: vec![10_000],
+        };
+        let err = BigDecimal::try_from(&numeric).unwrap_err();
+        assert!(matches!(err, ReplicationError::InvalidDigit(10_000)));
+    }
 }
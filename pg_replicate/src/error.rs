@@ -0,0 +1,119 @@
+//! Structured replication errors.
+//!
+//! Replaces ad-hoc `Box<dyn Error + Send + Sync>` and string errors with explicit variants so
+//! callers (sinks, the API layer, the retry policy in [`crate::pipeline::retry`]) can tell a
+//! recoverable decode glitch from a fatal type mismatch without string-matching.
+
+use std::error::Error;
+use std::fmt;
+
+/// A replication error, carrying enough context (column/type OID, byte offset, LSN) to act on
+/// programmatically rather than just log.
+#[derive(Debug)]
+pub enum ReplicationError {
+    /// A value can't be represented in the target type, e.g. NUMERIC `NaN` converted to
+    /// `BigDecimal`, or a `BigDecimal` whose scale or weight overflows the NUMERIC wire format.
+    UnsupportedNumericValue { type_oid: u32, reason: &'static str },
+    /// A NUMERIC's wire-format sign word was not one of `0`, `0x4000`, `0xC000`.
+    InvalidNumericSign(u16),
+    /// A NUMERIC's wire-format digit was outside the valid base-10000 range `[0, 9999]`.
+    InvalidDigit(i16),
+    /// The wire data for a column ended before all of its fields could be read.
+    TruncatedWireData {
+        type_oid: u32,
+        byte_offset: usize,
+        source: Box<dyn Error + Send + Sync>,
+    },
+    /// A `BigDecimal` <-> `rust_decimal::Decimal` conversion failed, e.g. the value's scale or
+    /// magnitude doesn't fit in `Decimal`'s narrower range.
+    RustDecimalConversion {
+        type_oid: u32,
+        source: Box<dyn Error + Send + Sync>,
+    },
+    /// A sink rejected a write. `permanent` distinguishes a fatal failure (schema mismatch,
+    /// auth failure, serialization error) from a transient one (connection reset, timeout,
+    /// rate limit) that the retry policy in [`crate::pipeline::retry`] should retry.
+    SinkWrite {
+        lsn: Option<u64>,
+        permanent: bool,
+        source: Box<dyn Error + Send + Sync>,
+    },
+    /// The upstream logical replication stream failed or was closed unexpectedly. `permanent`
+    /// is `true` for failures retrying won't fix, e.g. authentication or replication slot
+    /// errors, and `false` for connection drops worth reconnecting for.
+    SourceStream {
+        lsn: Option<u64>,
+        permanent: bool,
+        source: Box<dyn Error + Send + Sync>,
+    },
+}
+
+impl ReplicationError {
+    /// Whether retrying the operation that produced this error might succeed. `SinkWrite` and
+    /// `SourceStream` carry their own `permanent` flag since the same variant covers both
+    /// recoverable failures (timeouts, connection resets) and fatal ones (auth, schema
+    /// mismatches); every other variant is a decode/shape mismatch that retrying can't fix.
+    pub fn is_transient(&self) -> bool {
+        match self {
+            ReplicationError::SinkWrite { permanent, .. }
+            | ReplicationError::SourceStream { permanent, .. } => !permanent,
+            ReplicationError::UnsupportedNumericValue { .. }
+            | ReplicationError::InvalidNumericSign(_)
+            | ReplicationError::InvalidDigit(_)
+            | ReplicationError::TruncatedWireData { .. }
+            | ReplicationError::RustDecimalConversion { .. } => false,
+        }
+    }
+}
+
+impl fmt::Display for ReplicationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReplicationError::UnsupportedNumericValue { type_oid, reason } => {
+                write!(f, "unsupported value for type {type_oid}: {reason}")
+            }
+            ReplicationError::InvalidNumericSign(sign) => {
+                write!(
+                    f,
+                    "sign for numeric field was {sign:#06x}, expected one of 0, 0x4000, 0xC000"
+                )
+            }
+            ReplicationError::InvalidDigit(digit) => {
+                write!(f, "numeric digit {digit} is outside the valid range [0, 9999]")
+            }
+            ReplicationError::TruncatedWireData {
+                type_oid,
+                byte_offset,
+                ..
+            } => write!(
+                f,
+                "wire data for type {type_oid} was truncated at byte {byte_offset}"
+            ),
+            ReplicationError::RustDecimalConversion { type_oid, .. } => {
+                write!(f, "failed to convert type {type_oid} to/from rust_decimal::Decimal")
+            }
+            ReplicationError::SinkWrite { lsn, .. } => match lsn {
+                Some(lsn) => write!(f, "sink write failed at lsn {lsn}"),
+                None => write!(f, "sink write failed"),
+            },
+            ReplicationError::SourceStream { lsn, .. } => match lsn {
+                Some(lsn) => write!(f, "replication stream failed at lsn {lsn}"),
+                None => write!(f, "replication stream failed"),
+            },
+        }
+    }
+}
+
+impl Error for ReplicationError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ReplicationError::TruncatedWireData { source, .. }
+            | ReplicationError::RustDecimalConversion { source, .. }
+            | ReplicationError::SinkWrite { source, .. }
+            | ReplicationError::SourceStream { source, .. } => Some(source.as_ref()),
+            ReplicationError::UnsupportedNumericValue { .. }
+            | ReplicationError::InvalidNumericSign(_)
+            | ReplicationError::InvalidDigit(_) => None,
+        }
+    }
+}
@@ -0,0 +1,4 @@
+pub mod conversions;
+pub mod error;
+pub mod pipeline;
+pub mod table;
@@ -0,0 +1,92 @@
+//! Confirmed-LSN checkpointing so a pipeline can resume logical replication from where it
+//! left off instead of re-streaming from the slot's default position.
+//!
+//! `api::db::pipeline_checkpoints::PgCheckpointStore` is the Postgres-backed
+//! [`CheckpointStore`] implementation. [`resolve_stream_start`] turns a store's checkpoint into
+//! where a pipeline's replication stream should begin; nothing in this tree calls it on
+//! startup.`api::routes::pipelines::status` reports the checkpointed LSN through
+//! `GET /v1/pipelines/{id}/status`.
+
+use std::error::Error;
+
+use async_trait::async_trait;
+use tokio_postgres::types::PgLsn;
+
+use crate::pipeline::events::{EventBroadcaster, ReplicationEvent};
+use crate::pipeline::retry::{retry_sink_write_with_on_retry, RetryPolicy};
+
+/// Persists and retrieves the confirmed flush LSN for a pipeline, keyed by `pipeline_id`.
+///
+/// Implementations back this with the same Postgres-backed store the API already uses for
+/// pipeline/source/sink configuration, so a single store handles both.
+#[async_trait]
+pub trait CheckpointStore: Send + Sync {
+    /// Returns the last confirmed flush LSN for `pipeline_id`, or `None` if the pipeline has
+    /// never checkpointed (i.e. this is its first run).
+    async fn read_confirmed_lsn(
+        &self,
+        pipeline_id: i64,
+    ) -> Result<Option<PgLsn>, Box<dyn Error + Send + Sync>>;
+
+    /// Persists `lsn` as the confirmed flush LSN for `pipeline_id`. Called after each batch is
+    /// durably written to the sink.
+    async fn write_confirmed_lsn(
+        &self,
+        pipeline_id: i64,
+        lsn: PgLsn,
+    ) -> Result<(), Box<dyn Error + Send + Sync>>;
+}
+
+/// Where a replication stream should start: from the beginning of the slot (first run), or
+/// resumed from a previously checkpointed LSN.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StreamStart {
+    SlotDefault,
+    Resume(PgLsn),
+}
+
+/// Determines where to start streaming for `pipeline_id` by consulting `store`.
+pub async fn resolve_stream_start(
+    store: &dyn CheckpointStore,
+    pipeline_id: i64,
+) -> Result<StreamStart, Box<dyn Error + Send + Sync>> {
+    Ok(match store.read_confirmed_lsn(pipeline_id).await? {
+        Some(lsn) => StreamStart::Resume(lsn),
+        None => StreamStart::SlotDefault,
+    })
+}
+
+/// Writes a batch to the sink under `policy` (see [`crate::pipeline::retry`]), publishing a
+/// [`ReplicationEvent::Retry`] on `events` for each retry and a
+/// [`ReplicationEvent::SinkError`] if the write ultimately fails, and once it succeeds,
+/// persists `lsn` as the new confirmed flush LSN via `store`. Returns how many retries the
+/// write took, so callers can surface flapping sinks in pipeline status.
+pub async fn write_batch_with_retry<T, E, F, Fut>(
+    store: &dyn CheckpointStore,
+    events: &EventBroadcaster,
+    policy: &RetryPolicy,
+    pipeline_id: i64,
+    lsn: PgLsn,
+    write: F,
+) -> Result<u32, Box<dyn Error + Send + Sync>>
+where
+    E: Error + Send + Sync + 'static,
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = Result<T, E>>,
+{
+    let outcome = retry_sink_write_with_on_retry(policy, write, |attempt, err| {
+        events.publish(ReplicationEvent::Retry {
+            attempt,
+            reason: err.to_string(),
+        });
+    })
+    .await
+    .map_err(|err| {
+        events.publish(ReplicationEvent::SinkError {
+            reason: err.to_string(),
+        });
+        Box::new(err) as Box<dyn Error + Send + Sync>
+    })?;
+    store.write_confirmed_lsn(pipeline_id, lsn).await?;
+    Ok(outcome.retries)
+}
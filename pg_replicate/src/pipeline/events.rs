@@ -0,0 +1,67 @@
+//! Live replication events, pushed to subscribers (the API's SSE endpoint, tests, future
+//! dashboards/CLIs) by the replicator task as a pipeline runs.
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::broadcast;
+
+/// A discrete or periodic event emitted while a pipeline is running.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ReplicationEvent {
+    /// Emitted on a fixed interval so subscribers have a live view even when nothing else is
+    /// happening.
+    Heartbeat {
+        current_lsn: u64,
+        lag_bytes: u64,
+        rows_applied: u64,
+        last_event_unix_millis: u64,
+    },
+    /// The replication slot was created (or reused) for this pipeline.
+    SlotCreated { slot_name: String },
+    /// Progress on the initial backfill of a single table, see
+    /// [`crate::pipeline::snapshot`].
+    SnapshotProgress {
+        table_id: u32,
+        rows_copied: u64,
+        done: bool,
+    },
+    /// The sink rejected a write.
+    SinkError { reason: String },
+    /// A sink write is being retried, see [`crate::pipeline::retry`].
+    Retry { attempt: u32, reason: String },
+}
+
+/// How many events a subscriber can fall behind by before it starts missing ones; this is
+/// `tokio::sync::broadcast`'s own lag behavior.
+pub const EVENT_CHANNEL_CAPACITY: usize = 256;
+
+/// Publishes [`ReplicationEvent`]s for a single pipeline to any number of subscribers (the
+/// `/v1/pipelines/{id}/events` SSE endpoint, integration tests, ...).
+#[derive(Clone)]
+pub struct EventBroadcaster {
+    sender: broadcast::Sender<ReplicationEvent>,
+}
+
+impl EventBroadcaster {
+    pub fn new() -> Self {
+        let (sender, _) = broadcast::channel(EVENT_CHANNEL_CAPACITY);
+        Self { sender }
+    }
+
+    /// Subscribes to events published from now on. Missed a backlog? Subscribers only ever
+    /// see events emitted after they subscribe, same as a live tail.
+    pub fn subscribe(&self) -> broadcast::Receiver<ReplicationEvent> {
+        self.sender.subscribe()
+    }
+
+    /// Publishes `event` to all current subscribers. A no-op if nobody is listening.
+    pub fn publish(&self, event: ReplicationEvent) {
+        let _ = self.sender.send(event);
+    }
+}
+
+impl Default for EventBroadcaster {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,7 @@
+//! Pipeline runtime concerns: sink-write retries, LSN checkpointing, the initial snapshot
+//! phase, and the live event feed consumed by the API's SSE endpoint.
+
+pub mod checkpoint;
+pub mod events;
+pub mod retry;
+pub mod snapshot;
@@ -0,0 +1,343 @@
+//! Retry policy for sink writes.
+//!
+//! Wraps a single sink write so that transient failures (connection resets, timeouts,
+//! rate limits) are retried with exponential backoff and jitter, while permanent failures
+//! (schema mismatches, auth failures, serialization errors) are returned immediately.
+//!
+//! [`RetryConfig`] is the serializable form of a [`RetryPolicy`], exposed through
+//! `PipelineConfig` (see `api::db::pipelines`) so each pipeline created via the
+//! `create_pipeline` API can tune `max_retries`, `base_delay`, `max_delay`, and the retry-on
+//! classification. [`crate::pipeline::checkpoint::write_batch_with_retry`] is where a sink
+//! write is actually wrapped with the resulting policy.
+
+use std::error::Error;
+use std::fmt;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use tokio::time::sleep;
+
+use crate::error::ReplicationError;
+
+/// Whether a failed sink write is worth retrying.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RetryClassification {
+    /// The write may succeed if attempted again, e.g. a connection reset or timeout.
+    Transient,
+    /// The write will never succeed no matter how many times it is retried.
+    Permanent,
+}
+
+/// Classifies a sink write error as [`RetryClassification::Transient`] or
+/// [`RetryClassification::Permanent`].
+pub type RetryOn = Arc<dyn Fn(&(dyn Error + Send + Sync)) -> RetryClassification + Send + Sync>;
+
+/// The [`RetryOnConfig::ReplicationErrorTaxonomy`] classification: permanent if the error is a
+/// [`ReplicationError`] and [`ReplicationError::is_transient`] says so, transient otherwise
+/// (including for errors that aren't a `ReplicationError` at all, so unrecognized errors still
+/// get a chance to clear on their own). Shared by [`RetryPolicy::default`] and
+/// [`RetryOnConfig::into_retry_on`] so the two don't drift apart.
+fn default_retry_on(err: &(dyn Error + Send + Sync)) -> RetryClassification {
+    match err.downcast_ref::<ReplicationError>() {
+        Some(err) if !err.is_transient() => RetryClassification::Permanent,
+        _ => RetryClassification::Transient,
+    }
+}
+
+/// Exponential backoff with jitter, configurable per pipeline via `PipelineConfig`.
+///
+/// Delay for attempt `n` (0-indexed) is `min(base_delay * 2^n, max_delay)` plus a uniform
+/// random jitter in `[0, delay / 2]`.
+#[derive(Clone)]
+pub struct RetryPolicy {
+    pub max_retries: u32,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+    pub retry_on: RetryOn,
+}
+
+impl fmt::Debug for RetryPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RetryPolicy")
+            .field("max_retries", &self.max_retries)
+            .field("base_delay", &self.base_delay)
+            .field("max_delay", &self.max_delay)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Default for RetryPolicy {
+    /// Retries up to 5 times, starting at 100ms and capping at 30s. Classifies the error via
+    /// [`ReplicationError::is_transient`] when the sink raised one, and otherwise defaults to
+    /// transient so unrecognized errors still get a chance to clear on their own.
+    fn default() -> Self {
+        Self {
+            max_retries: 5,
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_secs(30),
+            retry_on: Arc::new(default_retry_on),
+        }
+    }
+}
+
+/// Which errors a retry policy should treat as worth retrying. `RetryOn` itself is a closure
+/// and can't be persisted, so `PipelineConfig` stores this instead and turns it into one via
+/// [`RetryConfig::to_policy`].
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RetryOnConfig {
+    /// Classify using [`ReplicationError::is_transient`] when the sink raised one, and
+    /// otherwise default to transient so unrecognized errors still get a chance to clear. This
+    /// is the default.
+    ReplicationErrorTaxonomy,
+    /// Treat every sink-write failure as transient and retry it up to `max_retries` times,
+    /// regardless of its type. Useful for sinks that haven't adopted `ReplicationError` yet.
+    AlwaysRetry,
+}
+
+impl Default for RetryOnConfig {
+    fn default() -> Self {
+        RetryOnConfig::ReplicationErrorTaxonomy
+    }
+}
+
+impl RetryOnConfig {
+    fn into_retry_on(self) -> RetryOn {
+        match self {
+            RetryOnConfig::ReplicationErrorTaxonomy => Arc::new(default_retry_on),
+            RetryOnConfig::AlwaysRetry => Arc::new(|_| RetryClassification::Transient),
+        }
+    }
+}
+
+/// The serializable knobs behind a [`RetryPolicy`], exposed through `PipelineConfig` so each
+/// pipeline created via the `create_pipeline` API can tune its own durability.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct RetryConfig {
+    pub max_retries: u32,
+    pub base_delay_ms: u64,
+    pub max_delay_ms: u64,
+    pub retry_on: RetryOnConfig,
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        let default_policy = RetryPolicy::default();
+        Self {
+            max_retries: default_policy.max_retries,
+            base_delay_ms: default_policy.base_delay.as_millis() as u64,
+            max_delay_ms: default_policy.max_delay.as_millis() as u64,
+            retry_on: RetryOnConfig::default(),
+        }
+    }
+}
+
+impl RetryConfig {
+    pub fn to_policy(self) -> RetryPolicy {
+        RetryPolicy {
+            max_retries: self.max_retries,
+            base_delay: Duration::from_millis(self.base_delay_ms),
+            max_delay: Duration::from_millis(self.max_delay_ms),
+            retry_on: self.retry_on.into_retry_on(),
+        }
+    }
+}
+
+impl RetryPolicy {
+    fn delay_for(&self, attempt: u32) -> Duration {
+        let factor = 1u32.checked_shl(attempt).unwrap_or(u32::MAX);
+        let exponential = self.base_delay.saturating_mul(factor).min(self.max_delay);
+        let jitter_ceiling_ms = (exponential.as_millis() / 2) as u64;
+        let jitter_ms = if jitter_ceiling_ms == 0 {
+            0
+        } else {
+            rand::thread_rng().gen_range(0..=jitter_ceiling_ms)
+        };
+        exponential + Duration::from_millis(jitter_ms)
+    }
+}
+
+/// The result of a retried sink write, including how many attempts it took beyond the first.
+#[derive(Debug)]
+pub struct RetryOutcome<T> {
+    pub value: T,
+    pub retries: u32,
+}
+
+/// Runs `write` under `policy`, retrying transient failures with exponential backoff and
+/// jitter up to `policy.max_retries` times. A permanent failure (per `policy.retry_on`) or
+/// exhausting the retry budget returns the last error.
+pub async fn retry_sink_write<T, E, F, Fut>(
+    policy: &RetryPolicy,
+    write: F,
+) -> Result<RetryOutcome<T>, E>
+where
+    E: Error + Send + Sync + 'static,
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+{
+    retry_sink_write_with_on_retry(policy, write, |_attempt, _err| {}).await
+}
+
+/// Like [`retry_sink_write`], but calls `on_retry(attempt, &err)` before each retry's backoff
+/// sleep, so a caller can publish a [`crate::pipeline::events::ReplicationEvent::Retry`] or
+/// similar without duplicating the retry loop.
+pub async fn retry_sink_write_with_on_retry<T, E, F, Fut>(
+    policy: &RetryPolicy,
+    mut write: F,
+    mut on_retry: impl FnMut(u32, &E),
+) -> Result<RetryOutcome<T>, E>
+where
+    E: Error + Send + Sync + 'static,
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+{
+    let mut retries = 0;
+    loop {
+        match write().await {
+            Ok(value) => return Ok(RetryOutcome { value, retries }),
+            Err(err) => {
+                let classification = (policy.retry_on)(&err);
+                if classification == RetryClassification::Permanent || retries >= policy.max_retries
+                {
+                    return Err(err);
+                }
+                on_retry(retries, &err);
+                sleep(policy.delay_for(retries)).await;
+                retries += 1;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+    use std::fmt;
+
+    #[derive(Debug)]
+    struct FlakyError(bool);
+
+    impl fmt::Display for FlakyError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "flaky error (permanent: {})", self.0)
+        }
+    }
+
+    impl Error for FlakyError {}
+
+    #[tokio::test]
+    async fn retries_transient_errors_until_success() {
+        let attempts = Cell::new(0);
+        let policy = RetryPolicy {
+            max_retries: 3,
+            base_delay: Duration::from_millis(1),
+            max_delay: Duration::from_millis(5),
+            ..RetryPolicy::default()
+        };
+
+        let outcome = retry_sink_write(&policy, || {
+            attempts.set(attempts.get() + 1);
+            let attempt = attempts.get();
+            async move {
+                if attempt < 3 {
+                    Err(FlakyError(false))
+                } else {
+                    Ok(())
+                }
+            }
+        })
+        .await
+        .expect("should eventually succeed");
+
+        assert_eq!(outcome.retries, 2);
+        assert_eq!(attempts.get(), 3);
+    }
+
+    #[tokio::test]
+    async fn permanent_errors_are_not_retried() {
+        let attempts = Cell::new(0);
+        let policy = RetryPolicy {
+            max_retries: 5,
+            base_delay: Duration::from_millis(1),
+            max_delay: Duration::from_millis(5),
+            retry_on: Arc::new(|_| RetryClassification::Permanent),
+        };
+
+        let result = retry_sink_write(&policy, || {
+            attempts.set(attempts.get() + 1);
+            async move { Err::<(), _>(FlakyError(true)) }
+        })
+        .await;
+
+        assert!(result.is_err());
+        assert_eq!(attempts.get(), 1);
+    }
+
+    #[tokio::test]
+    async fn exhausts_retry_budget() {
+        let attempts = Cell::new(0);
+        let policy = RetryPolicy {
+            max_retries: 2,
+            base_delay: Duration::from_millis(1),
+            max_delay: Duration::from_millis(5),
+            ..RetryPolicy::default()
+        };
+
+        let result = retry_sink_write(&policy, || {
+            attempts.set(attempts.get() + 1);
+            async move { Err::<(), _>(FlakyError(false)) }
+        })
+        .await;
+
+        assert!(result.is_err());
+        assert_eq!(attempts.get(), 3);
+    }
+
+    #[test]
+    fn retry_config_round_trips_through_policy() {
+        let config = RetryConfig {
+            max_retries: 7,
+            base_delay_ms: 50,
+            max_delay_ms: 1_000,
+            retry_on: RetryOnConfig::AlwaysRetry,
+        };
+        let policy = config.to_policy();
+        assert_eq!(policy.max_retries, 7);
+        assert_eq!(policy.base_delay, Duration::from_millis(50));
+        assert_eq!(policy.max_delay, Duration::from_millis(1_000));
+    }
+
+    #[tokio::test]
+    async fn always_retry_config_retries_non_replication_errors() {
+        let attempts = Cell::new(0);
+        let policy = RetryConfig {
+            max_retries: 2,
+            base_delay_ms: 1,
+            max_delay_ms: 5,
+            retry_on: RetryOnConfig::AlwaysRetry,
+        }
+        .to_policy();
+
+        let outcome = retry_sink_write(&policy, || {
+            attempts.set(attempts.get() + 1);
+            let attempt = attempts.get();
+            async move {
+                if attempt < 2 {
+                    Err(FlakyError(false))
+                } else {
+                    Ok(())
+                }
+            }
+        })
+        .await
+        .expect("should eventually succeed");
+
+        assert_eq!(outcome.retries, 1);
+    }
+}
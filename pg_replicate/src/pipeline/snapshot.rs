@@ -0,0 +1,295 @@
+//! Initial consistent snapshot (backfill) phase, run before logical streaming begins.
+//!
+//! [`run_snapshot_phase`] copies each published table in primary-key-ordered chunks through a
+//! caller-supplied copy function, then returns the [`ExportedSnapshot`]'s consistent LSN as
+//! the point streaming resumes from. This module does not create the replication slot, export
+//! the snapshot or open the snapshot transaction; callers pass the exported snapshot in.
+
+use std::error::Error;
+
+use serde::{Deserialize, Serialize};
+use tokio_postgres::types::PgLsn;
+
+use crate::pipeline::checkpoint::StreamStart;
+use crate::pipeline::events::{EventBroadcaster, ReplicationEvent};
+use crate::table::TableId;
+
+/// Whether a pipeline backfills existing table contents before streaming, and how, exposed
+/// through `PipelineConfig` (see `api::db::pipelines`).
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct SnapshotConfig {
+    /// Run the initial backfill before streaming. Disabled by default so existing pipelines
+    /// keep their current behavior of only ever seeing changes from the point they start.
+    pub enabled: bool,
+    /// How many rows to `COPY` per chunk, checkpointing the cursor in between.
+    pub chunk_size: u32,
+}
+
+impl Default for SnapshotConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            chunk_size: 10_000,
+        }
+    }
+}
+
+/// A single table's progress through the backfill, keyed by the last primary key seen so an
+/// interrupted backfill resumes mid-table rather than restarting it from scratch.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TableBackfillCursor {
+    pub table_id: TableId,
+    /// The primary key of the last row copied, encoded as its text representation so it works
+    /// across composite and differently-typed primary keys.
+    pub last_pk: Option<String>,
+    pub rows_copied: u64,
+    pub done: bool,
+}
+
+impl TableBackfillCursor {
+    pub fn start(table_id: TableId) -> Self {
+        Self {
+            table_id,
+            last_pk: None,
+            rows_copied: 0,
+            done: false,
+        }
+    }
+}
+
+/// The exported snapshot a backfill runs against, plus the LSN streaming should resume from
+/// once every table has finished copying.
+#[derive(Debug, Clone)]
+pub struct ExportedSnapshot {
+    pub snapshot_name: String,
+    pub consistent_lsn: PgLsn,
+}
+
+/// Persists and loads per-table backfill cursors, so a restarted pipeline resumes a backfill
+/// in progress instead of re-copying tables it already finished.
+#[async_trait::async_trait]
+pub trait SnapshotCheckpointStore: Send + Sync {
+    async fn read_cursor(
+        &self,
+        pipeline_id: i64,
+        table_id: TableId,
+    ) -> Result<Option<TableBackfillCursor>, Box<dyn Error + Send + Sync>>;
+
+    async fn write_cursor(
+        &self,
+        pipeline_id: i64,
+        cursor: &TableBackfillCursor,
+    ) -> Result<(), Box<dyn Error + Send + Sync>>;
+}
+
+/// Copies `table_id` in primary-key-ordered chunks of `chunk_size` rows, checkpointing the
+/// cursor after each chunk via `store`, and calling `emit_row` for every copied row so the
+/// sink sees the same row events it would during streaming.
+///
+/// Picks up from `store`'s saved cursor for this table if one exists, so re-running this
+/// after an interruption resumes instead of restarting.
+pub async fn backfill_table<E, F>(
+    store: &dyn SnapshotCheckpointStore,
+    pipeline_id: i64,
+    table_id: TableId,
+    chunk_size: u32,
+    mut copy_chunk: impl FnMut(
+        Option<&str>,
+        u32,
+    ) -> futures::future::BoxFuture<'static, Result<Vec<(String, E)>, Box<dyn Error + Send + Sync>>>,
+    mut emit_row: F,
+) -> Result<TableBackfillCursor, Box<dyn Error + Send + Sync>>
+where
+    F: FnMut(&E),
+{
+    let mut cursor = store
+        .read_cursor(pipeline_id, table_id)
+        .await?
+        .unwrap_or_else(|| TableBackfillCursor::start(table_id));
+
+    if cursor.done {
+        return Ok(cursor);
+    }
+
+    loop {
+        let rows = copy_chunk(cursor.last_pk.as_deref(), chunk_size).await?;
+        if rows.is_empty() {
+            cursor.done = true;
+            store.write_cursor(pipeline_id, &cursor).await?;
+            return Ok(cursor);
+        }
+
+        let copied = rows.len() as u64;
+        for (pk, row) in &rows {
+            emit_row(row);
+            cursor.last_pk = Some(pk.clone());
+        }
+        cursor.rows_copied += copied;
+
+        if copied < chunk_size as u64 {
+            cursor.done = true;
+        }
+        store.write_cursor(pipeline_id, &cursor).await?;
+
+        if cursor.done {
+            return Ok(cursor);
+        }
+    }
+}
+
+/// Runs [`backfill_table`] for every table in `table_ids`, in order, and once all of them have
+/// finished, returns where logical streaming should resume from: `snapshot`'s consistent LSN.
+/// Tables already marked `done` in `store` (from a prior, interrupted run) are skipped over
+/// almost immediately by `backfill_table` itself, so re-running this after a restart resumes
+/// mid-backfill instead of re-copying finished tables.
+pub async fn run_snapshot_phase<E, F>(
+    store: &dyn SnapshotCheckpointStore,
+    events: &EventBroadcaster,
+    pipeline_id: i64,
+    snapshot: &ExportedSnapshot,
+    table_ids: &[TableId],
+    chunk_size: u32,
+    mut copy_chunk: impl FnMut(
+        TableId,
+        Option<&str>,
+        u32,
+    ) -> futures::future::BoxFuture<'static, Result<Vec<(String, E)>, Box<dyn Error + Send + Sync>>>,
+    mut emit_row: F,
+) -> Result<StreamStart, Box<dyn Error + Send + Sync>>
+where
+    F: FnMut(TableId, &E),
+{
+    for &table_id in table_ids {
+        let cursor = backfill_table(
+            store,
+            pipeline_id,
+            table_id,
+            chunk_size,
+            |last_pk, n| copy_chunk(table_id, last_pk, n),
+            |row| emit_row(table_id, row),
+        )
+        .await?;
+        events.publish(ReplicationEvent::SnapshotProgress {
+            table_id: table_id.0,
+            rows_copied: cursor.rows_copied,
+            done: cursor.done,
+        });
+    }
+    Ok(StreamStart::Resume(snapshot.consistent_lsn))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct InMemoryStore {
+        cursors: Mutex<HashMap<TableId, TableBackfillCursor>>,
+    }
+
+    #[async_trait::async_trait]
+    impl SnapshotCheckpointStore for InMemoryStore {
+        async fn read_cursor(
+            &self,
+            _pipeline_id: i64,
+            table_id: TableId,
+        ) -> Result<Option<TableBackfillCursor>, Box<dyn Error + Send + Sync>> {
+            Ok(self.cursors.lock().unwrap().get(&table_id).cloned())
+        }
+
+        async fn write_cursor(
+            &self,
+            _pipeline_id: i64,
+            cursor: &TableBackfillCursor,
+        ) -> Result<(), Box<dyn Error + Send + Sync>> {
+            self.cursors
+                .lock()
+                .unwrap()
+                .insert(cursor.table_id, cursor.clone());
+            Ok(())
+        }
+    }
+
+    fn chunk(rows: &[&str]) -> Vec<(String, String)> {
+        rows.iter().map(|pk| (pk.to_string(), pk.to_string())).collect()
+    }
+
+    #[tokio::test]
+    async fn resumes_mid_table_after_an_interrupted_chunk() {
+        let store = InMemoryStore::default();
+        let table_id = TableId(1);
+
+        // First chunk succeeds and checkpoints; the run is then interrupted before the second.
+        let mut calls = 0;
+        let result = backfill_table(
+            &store,
+            7,
+            table_id,
+            2,
+            |last_pk, _n| {
+                if calls == 1 {
+                    return Box::pin(async { Err("interrupted".into()) });
+                }
+                calls += 1;
+                assert_eq!(last_pk, None, "first call should start from the beginning");
+                Box::pin(async { Ok(chunk(&["a", "b"])) })
+            },
+            |_row: &String| {},
+        )
+        .await;
+        assert!(result.is_err());
+        let saved = store.cursors.lock().unwrap().get(&table_id).cloned().unwrap();
+        assert_eq!(saved.rows_copied, 2);
+        assert!(!saved.done);
+
+        // Simulate a restart: re-run against the same store, which should pick up the
+        // checkpointed cursor instead of starting over from `None`.
+        let result = backfill_table(
+            &store,
+            7,
+            table_id,
+            2,
+            |last_pk, _n| {
+                assert_eq!(last_pk.as_deref(), Some("b"), "should resume after the last checkpoint");
+                Box::pin(async { Ok(Vec::<(String, String)>::new()) })
+            },
+            |_row: &String| {},
+        )
+        .await
+        .unwrap();
+        assert_eq!(calls, 1);
+        assert_eq!(result.rows_copied, 2);
+        assert!(result.done);
+    }
+
+    #[tokio::test]
+    async fn already_done_table_is_skipped_without_copying() {
+        let store = InMemoryStore::default();
+        let table_id = TableId(2);
+        store.cursors.lock().unwrap().insert(
+            table_id,
+            TableBackfillCursor {
+                table_id,
+                last_pk: Some("z".to_string()),
+                rows_copied: 5,
+                done: true,
+            },
+        );
+
+        let result = backfill_table(
+            &store,
+            7,
+            table_id,
+            2,
+            |_last_pk, _n| panic!("should not copy a table already marked done"),
+            |_row: &String| {},
+        )
+        .await
+        .unwrap();
+        assert!(result.done);
+        assert_eq!(result.rows_copied, 5);
+    }
+}
@@ -0,0 +1,6 @@
+//! Identifiers for replicated tables.
+
+/// A Postgres table's OID, used to key per-table state such as backfill cursors (see
+/// [`crate::pipeline::snapshot`]).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct TableId(pub u32);